@@ -0,0 +1,149 @@
+use crate::terminal::Terminal;
+use failure::Fail;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// The error raised by `ShellCommand::run` when the underlying process exits
+/// with a non-zero status. Carries enough context (program, arguments,
+/// captured stderr) for callers to produce a useful diagnostic.
+#[derive(Fail, Debug)]
+#[fail(display = "{} {} failed: {}", program, args, stderr)]
+pub struct CommandFailed {
+    program: String,
+    args: String,
+    stderr: String,
+}
+
+/// A small builder around `std::process::Command` that every step function
+/// goes through. It centralizes elevation via `sudo`, stdio handling, and
+/// failure diagnostics so step functions no longer have to duplicate that
+/// logic themselves.
+pub struct ShellCommand<'a> {
+    program: String,
+    args: Vec<String>,
+    current_dir: Option<&'a Path>,
+    sudo: Option<&'a Path>,
+    capture_output: bool,
+}
+
+impl<'a> ShellCommand<'a> {
+    pub fn new<S: AsRef<str>>(program: S) -> Self {
+        ShellCommand {
+            program: program.as_ref().to_string(),
+            args: Vec::new(),
+            current_dir: None,
+            sudo: None,
+            capture_output: true,
+        }
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.args
+            .extend(args.into_iter().map(|arg| arg.as_ref().to_string()));
+        self
+    }
+
+    pub fn current_dir(mut self, dir: &'a Path) -> Self {
+        self.current_dir = Some(dir);
+        self
+    }
+
+    pub fn sudo(mut self, sudo: Option<&'a Path>) -> Self {
+        self.sudo = sudo;
+        self
+    }
+
+    pub fn capture_output(mut self, capture_output: bool) -> Self {
+        self.capture_output = capture_output;
+        self
+    }
+
+    fn build(&self) -> Command {
+        match self.sudo {
+            Some(sudo) => {
+                let mut command = Command::new(sudo);
+                command.arg(&self.program).args(&self.args);
+                command
+            }
+            None => {
+                let mut command = Command::new(&self.program);
+                command.args(&self.args);
+                command
+            }
+        }
+    }
+
+    /// The fully-resolved command line, including the `sudo` prefix if any,
+    /// with program and arguments joined by spaces. Arguments are not
+    /// quoted, so one that contains spaces is not marked off.
+    fn resolved(&self) -> String {
+        let mut parts = Vec::new();
+
+        if let Some(sudo) = self.sudo {
+            parts.push(sudo.display().to_string());
+        }
+
+        parts.push(self.program.clone());
+        parts.extend(self.args.iter().cloned());
+
+        parts.join(" ")
+    }
+
+    /// Run the command, inheriting the parent's stdio unless
+    /// `capture_output` was requested, in which case stderr is captured so
+    /// it can be attached to the returned error. In `--dry-run` mode,
+    /// prints the resolved command instead of spawning it. On failure, the
+    /// error is also printed via `terminal.print_error` before being
+    /// returned, so every step gets the same diagnostic regardless of
+    /// whether its call site looks at the `Result` any further.
+    pub fn run(&self, terminal: &mut Terminal) -> Result<(), failure::Error> {
+        if terminal.dry_run() {
+            terminal.print_line(format!("Would run: {}", self.resolved()));
+            return Ok(());
+        }
+
+        let mut command = self.build();
+
+        if let Some(dir) = self.current_dir {
+            command.current_dir(dir);
+        }
+
+        debug!("Running {:?} with {:?}", self.program, self.args);
+
+        let args = self.args.join(" ");
+
+        let result: Result<(), failure::Error> = if self.capture_output {
+            match command.stdout(Stdio::inherit()).stderr(Stdio::piped()).output() {
+                Ok(ref output) if output.status.success() => Ok(()),
+                Ok(output) => Err(CommandFailed {
+                    program: self.program.clone(),
+                    args,
+                    stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+                }
+                .into()),
+                Err(error) => Err(error.into()),
+            }
+        } else {
+            match command.status() {
+                Ok(status) if status.success() => Ok(()),
+                Ok(_) => Err(CommandFailed {
+                    program: self.program.clone(),
+                    args,
+                    stderr: String::new(),
+                }
+                .into()),
+                Err(error) => Err(error.into()),
+            }
+        };
+
+        if let Err(ref failed) = result {
+            terminal.print_error(failed.to_string());
+        }
+
+        result
+    }
+}
@@ -0,0 +1,94 @@
+use directories::BaseDirs;
+use failure::Error;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// A single entry of the `[commands]`/`[pre_commands]` tables. Most users
+/// only need the same invocation on every platform, so the plain string
+/// form is kept working; an entry can also be a table giving a different
+/// command per platform (and an optional working directory) for the rare
+/// step that needs it.
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+pub enum CustomCommand {
+    Plain(String),
+    Platform {
+        unix: Option<String>,
+        windows: Option<String>,
+        cwd: Option<String>,
+    },
+}
+
+impl CustomCommand {
+    /// The invocation for the current platform, if one was given.
+    pub fn command(&self) -> Option<&str> {
+        match self {
+            CustomCommand::Plain(command) => Some(command.as_str()),
+            CustomCommand::Platform { unix, windows, .. } => {
+                if cfg!(windows) {
+                    windows.as_ref().map(String::as_str)
+                } else {
+                    unix.as_ref().map(String::as_str)
+                }
+            }
+        }
+    }
+
+    /// The working directory the command should run in, if one was given.
+    pub fn work_dir(&self) -> Option<&str> {
+        match self {
+            CustomCommand::Plain(_) => None,
+            CustomCommand::Platform { cwd, .. } => cwd.as_ref().map(String::as_str),
+        }
+    }
+}
+
+/// Deserialized form of `~/.config/topgrade.toml`. All fields are optional
+/// so an absent or partial config file is perfectly valid.
+#[derive(Deserialize, Default, Clone)]
+pub struct Config {
+    pre_commands: Option<HashMap<String, CustomCommand>>,
+    commands: Option<HashMap<String, CustomCommand>>,
+    git_repos: Option<Vec<String>>,
+    skip: Option<Vec<String>>,
+}
+
+impl Config {
+    /// Read the config file from the user's config directory. A missing
+    /// file is treated as an empty config rather than an error.
+    pub fn read(base_dirs: &BaseDirs) -> Result<Self, Error> {
+        let config_path = Self::config_path(base_dirs);
+
+        if !config_path.exists() {
+            return Ok(Config::default());
+        }
+
+        let content = fs::read_to_string(config_path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    fn config_path(base_dirs: &BaseDirs) -> PathBuf {
+        base_dirs.config_dir().join("topgrade.toml")
+    }
+
+    pub fn pre_commands(&self) -> Option<HashMap<String, CustomCommand>> {
+        self.pre_commands.clone()
+    }
+
+    pub fn commands(&self) -> Option<HashMap<String, CustomCommand>> {
+        self.commands.clone()
+    }
+
+    pub fn git_repos(&self) -> Option<Vec<PathBuf>> {
+        self.git_repos
+            .clone()
+            .map(|repos| repos.into_iter().map(PathBuf::from).collect())
+    }
+
+    /// The ids of the steps that should always be skipped, as configured
+    /// by the `[skip]` list.
+    pub fn skip(&self) -> Vec<String> {
+        self.skip.clone().unwrap_or_default()
+    }
+}
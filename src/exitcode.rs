@@ -0,0 +1,107 @@
+/// Exit codes returned by the process. Beyond `Success`/`MixedFailures`,
+/// each variant identifies the kind of step that failed so wrapper scripts
+/// and cron jobs can react to, say, a failed git pull differently from a
+/// failed system upgrade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppExitCode {
+    Success = 0,
+    MixedFailures = 1,
+    SystemUpgradeFailed = 2,
+    GitFailed = 3,
+    PackageManagerFailed = 4,
+    CustomCommandFailed = 5,
+}
+
+impl AppExitCode {
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+/// The category a step belongs to, used to pick which `AppExitCode` to
+/// report when that step fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepCategory {
+    SystemUpgrade,
+    Git,
+    PackageManager,
+    CustomCommand,
+    Other,
+}
+
+impl StepCategory {
+    fn exit_code(self) -> AppExitCode {
+        match self {
+            StepCategory::SystemUpgrade => AppExitCode::SystemUpgradeFailed,
+            StepCategory::Git => AppExitCode::GitFailed,
+            StepCategory::PackageManager => AppExitCode::PackageManagerFailed,
+            StepCategory::CustomCommand => AppExitCode::CustomCommandFailed,
+            StepCategory::Other => AppExitCode::MixedFailures,
+        }
+    }
+}
+
+/// Categories in priority order: when several kinds of steps fail in the
+/// same run, the highest-priority one determines the process exit code.
+const PRIORITY: [StepCategory; 4] = [
+    StepCategory::SystemUpgrade,
+    StepCategory::Git,
+    StepCategory::PackageManager,
+    StepCategory::CustomCommand,
+];
+
+/// Pick the exit code for a run given the categories of its failed steps.
+pub fn highest_priority_failure(failed_categories: &[StepCategory]) -> AppExitCode {
+    if failed_categories.is_empty() {
+        return AppExitCode::Success;
+    }
+
+    PRIORITY
+        .iter()
+        .find(|category| failed_categories.contains(category))
+        .map(|category| category.exit_code())
+        .unwrap_or(AppExitCode::MixedFailures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_failures_is_success() {
+        assert_eq!(highest_priority_failure(&[]), AppExitCode::Success);
+    }
+
+    #[test]
+    fn single_category_maps_to_its_own_code() {
+        assert_eq!(
+            highest_priority_failure(&[StepCategory::Git]),
+            AppExitCode::GitFailed
+        );
+    }
+
+    #[test]
+    fn system_upgrade_outranks_git_and_package_manager() {
+        let failed = [
+            StepCategory::CustomCommand,
+            StepCategory::PackageManager,
+            StepCategory::Git,
+            StepCategory::SystemUpgrade,
+        ];
+        assert_eq!(highest_priority_failure(&failed), AppExitCode::SystemUpgradeFailed);
+    }
+
+    #[test]
+    fn git_outranks_package_manager_and_custom_command() {
+        let failed = [StepCategory::CustomCommand, StepCategory::PackageManager, StepCategory::Git];
+        assert_eq!(highest_priority_failure(&failed), AppExitCode::GitFailed);
+    }
+
+    #[test]
+    fn category_outside_priority_list_falls_back_to_mixed_failures() {
+        assert_eq!(
+            highest_priority_failure(&[StepCategory::Other]),
+            AppExitCode::MixedFailures
+        );
+    }
+}
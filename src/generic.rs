@@ -0,0 +1,90 @@
+use crate::commands::ShellCommand;
+use crate::config::CustomCommand;
+use crate::terminal::Terminal;
+use crate::utils;
+use directories::BaseDirs;
+use failure::Error;
+use std::path::Path;
+
+/// Run a single user-defined command from the `[commands]` or
+/// `[pre_commands]` tables in the config file, picking the invocation
+/// for the current platform and running it in its configured working
+/// directory, if any. Returns `Ok(false)` rather than running anything
+/// when the command has no invocation for the current platform, so
+/// callers can tell "not applicable here" apart from "ran successfully".
+pub fn run_custom_command(name: &str, command: &CustomCommand, terminal: &mut Terminal) -> Result<bool, Error> {
+    let command_str = match command.command() {
+        Some(command_str) => command_str,
+        None => return Ok(false),
+    };
+
+    terminal.print_separator(name);
+
+    let mut shell_command = ShellCommand::new("sh").args(&["-c", command_str]);
+
+    if let Some(work_dir) = command.work_dir() {
+        shell_command = shell_command.current_dir(Path::new(work_dir));
+    }
+
+    shell_command.run(terminal)?;
+    Ok(true)
+}
+
+pub fn run_rustup(base_dirs: &BaseDirs, terminal: &mut Terminal) -> Option<(String, bool)> {
+    let rustup = utils::which("rustup")?;
+    terminal.print_separator("rustup");
+
+    let success = ShellCommand::new(rustup.to_str()?)
+        .args(&["update"])
+        .run(terminal)
+        .is_ok();
+
+    let _ = base_dirs;
+    Some(("rustup".to_string(), success))
+}
+
+pub fn run_cargo_update(base_dirs: &BaseDirs, terminal: &mut Terminal) -> Option<(String, bool)> {
+    let cargo_update = base_dirs.home_dir().join(".cargo/bin/cargo-install-update");
+    if !cargo_update.exists() {
+        return None;
+    }
+    terminal.print_separator("Cargo");
+
+    let success = ShellCommand::new(cargo_update.to_str()?)
+        .args(&["install-update", "--git", "--all"])
+        .run(terminal)
+        .is_ok();
+
+    Some(("Cargo".to_string(), success))
+}
+
+pub fn run_emacs(base_dirs: &BaseDirs, terminal: &mut Terminal) -> Option<(String, bool)> {
+    let emacs_dir = base_dirs.home_dir().join(".emacs.d");
+    let init_file = emacs_dir.join("init.el");
+    if !init_file.exists() {
+        return None;
+    }
+
+    let emacs = utils::which("emacs")?;
+    terminal.print_separator("Emacs");
+
+    let success = ShellCommand::new(emacs.to_str()?)
+        .args(&["--batch", "-l", init_file.to_str()?, "--eval", "(topgrade-upgrade)"])
+        .current_dir(&emacs_dir)
+        .run(terminal)
+        .is_ok();
+
+    Some(("Emacs".to_string(), success))
+}
+
+pub fn run_apm(terminal: &mut Terminal) -> Option<(String, bool)> {
+    let apm = utils::which("apm")?;
+    terminal.print_separator("Atom Package Manager");
+
+    let success = ShellCommand::new(apm.to_str()?)
+        .args(&["upgrade", "--confirm=false"])
+        .run(terminal)
+        .is_ok();
+
+    Some(("apm".to_string(), success))
+}
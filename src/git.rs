@@ -0,0 +1,64 @@
+use crate::commands::ShellCommand;
+use crate::terminal::Terminal;
+use crate::utils;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Wraps the `git` binary, if found, and knows how to pull a single
+/// repository.
+pub struct Git {
+    git: Option<PathBuf>,
+}
+
+impl Git {
+    pub fn new() -> Self {
+        Git { git: utils::which("git") }
+    }
+
+    fn is_git_repo(path: &Path) -> bool {
+        path.is_dir() && path.join(".git").exists()
+    }
+
+    pub fn pull(&self, repo: &Path, terminal: &mut Terminal) -> Option<(String, bool)> {
+        let git = self.git.as_ref()?;
+        let repo_name = repo.to_str()?.to_string();
+
+        terminal.print_separator(format!("Pulling {}", repo_name));
+
+        let success = ShellCommand::new(git.to_str()?)
+            .args(&["pull", "--rebase", "--autostash"])
+            .current_dir(repo)
+            .run(terminal)
+            .is_ok();
+
+        Some((repo_name, success))
+    }
+}
+
+/// Deduplicated set of git repositories to pull, collected from the well
+/// known dotfile locations plus whatever the user added in their config.
+pub struct Repositories<'a> {
+    #[allow(dead_code)]
+    git: &'a Git,
+    repos: HashSet<PathBuf>,
+}
+
+impl<'a> Repositories<'a> {
+    pub fn new(git: &'a Git) -> Self {
+        Repositories {
+            git,
+            repos: HashSet::new(),
+        }
+    }
+
+    pub fn insert<P: Into<PathBuf>>(&mut self, path: P) {
+        let path = path.into();
+        if Git::is_git_repo(&path) {
+            self.repos.insert(path);
+        }
+    }
+
+    pub fn repositories(&self) -> Vec<PathBuf> {
+        self.repos.iter().cloned().collect()
+    }
+}
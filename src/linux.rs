@@ -0,0 +1,90 @@
+use crate::commands::ShellCommand;
+use crate::terminal::Terminal;
+use crate::utils;
+use std::path::PathBuf;
+
+pub fn upgrade(sudo: &Option<PathBuf>, terminal: &mut Terminal) -> Option<(String, bool)> {
+    terminal.print_separator("System update");
+
+    let success = if let Some(pacman) = utils::which("pacman") {
+        ShellCommand::new(pacman.to_str()?)
+            .args(&["-Syu"])
+            .sudo(sudo.as_deref())
+            .run(terminal)
+            .is_ok()
+    } else if let Some(apt) = utils::which("apt") {
+        ShellCommand::new(apt.to_str()?)
+            .args(&["update"])
+            .sudo(sudo.as_deref())
+            .run(terminal)
+            .is_ok()
+            && ShellCommand::new(apt.to_str()?)
+                .args(&["dist-upgrade"])
+                .sudo(sudo.as_deref())
+                .run(terminal)
+                .is_ok()
+    } else if let Some(dnf) = utils::which("dnf") {
+        ShellCommand::new(dnf.to_str()?)
+            .args(&["upgrade"])
+            .sudo(sudo.as_deref())
+            .run(terminal)
+            .is_ok()
+    } else {
+        return None;
+    };
+
+    Some(("System update".to_string(), success))
+}
+
+pub fn run_flatpak(terminal: &mut Terminal) -> Option<(String, bool)> {
+    let flatpak = utils::which("flatpak")?;
+    terminal.print_separator("Flatpak");
+
+    let success = ShellCommand::new(flatpak.to_str()?)
+        .args(&["update"])
+        .run(terminal)
+        .is_ok();
+
+    Some(("Flatpak".to_string(), success))
+}
+
+pub fn run_snap(sudo: &Option<PathBuf>, terminal: &mut Terminal) -> Option<(String, bool)> {
+    let snap = utils::which("snap")?;
+    terminal.print_separator("snap");
+
+    let success = ShellCommand::new(snap.to_str()?)
+        .args(&["refresh"])
+        .sudo(sudo.as_deref())
+        .run(terminal)
+        .is_ok();
+
+    Some(("snap".to_string(), success))
+}
+
+pub fn run_fwupdmgr(terminal: &mut Terminal) -> Option<(String, bool)> {
+    let fwupdmgr = utils::which("fwupdmgr")?;
+    terminal.print_separator("Firmware upgrades");
+
+    let success = ShellCommand::new(fwupdmgr.to_str()?)
+        .args(&["refresh"])
+        .run(terminal)
+        .is_ok()
+        && ShellCommand::new(fwupdmgr.to_str()?)
+            .args(&["get-updates"])
+            .run(terminal)
+            .is_ok();
+
+    Some(("Firmware upgrades".to_string(), success))
+}
+
+pub fn run_needrestart(sudo: &Option<PathBuf>, terminal: &mut Terminal) -> Option<(String, bool)> {
+    let needrestart = utils::which("needrestart")?;
+    terminal.print_separator("Check for needed restarts");
+
+    let success = ShellCommand::new(needrestart.to_str()?)
+        .sudo(sudo.as_deref())
+        .run(terminal)
+        .is_ok();
+
+    Some(("Restarts".to_string(), success))
+}
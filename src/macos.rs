@@ -0,0 +1,13 @@
+use crate::commands::ShellCommand;
+use crate::terminal::Terminal;
+
+pub fn upgrade_macos(terminal: &mut Terminal) -> Option<(String, bool)> {
+    terminal.print_separator("App Store");
+
+    let success = ShellCommand::new("softwareupdate")
+        .args(&["--install", "--all"])
+        .run(terminal)
+        .is_ok();
+
+    Some(("App Store".to_string(), success))
+}
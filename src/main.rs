@@ -25,33 +25,34 @@ mod unix;
 #[cfg(target_os = "windows")]
 mod windows;
 
+mod commands;
 mod config;
+mod exitcode;
 mod generic;
 mod git;
 mod node;
 mod report;
+mod steps;
 mod terminal;
 mod utils;
 mod vim;
 
 use self::config::Config;
+use self::exitcode::{AppExitCode, StepCategory};
 use self::git::{Git, Repositories};
 use self::report::Report;
+use self::steps::Step;
 use self::terminal::Terminal;
 use clap::{App, Arg};
 use failure::Error;
 use std::env;
 use std::process::exit;
 
-#[derive(Fail, Debug)]
-#[fail(display = "A step failed")]
-struct StepFailed;
-
 #[derive(Fail, Debug)]
 #[fail(display = "Cannot find the user base directories")]
 struct NoBaseDirectories;
 
-fn run() -> Result<(), Error> {
+fn run() -> Result<AppExitCode, Error> {
     let matches = App::new("Topgrade")
         .version(crate_version!())
         .about("Upgrade all the things")
@@ -66,6 +67,30 @@ fn run() -> Result<(), Error> {
                 .help("Don't perform system upgrade")
                 .long("no-system"),
         )
+        .arg(
+            Arg::with_name("dry_run")
+                .help("Print what would be run, without executing anything")
+                .long("dry-run"),
+        )
+        .arg(
+            Arg::with_name("only")
+                .help("Only run the given comma-separated steps")
+                .long("only")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("skip")
+                .help("Don't run the given comma-separated steps")
+                .long("skip")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("retry")
+                .help("Retry failed steps once the first pass completes, up to N times (default 1)")
+                .long("retry")
+                .takes_value(true)
+                .min_values(0),
+        )
         .get_matches();
 
     if matches.is_present("tmux") && env::var("TMUX").is_err() {
@@ -79,7 +104,7 @@ fn run() -> Result<(), Error> {
     let base_dirs = directories::BaseDirs::new().ok_or(NoBaseDirectories)?;
     let git = Git::new();
     let mut git_repos = Repositories::new(&git);
-    let mut terminal = Terminal::new();
+    let mut terminal = Terminal::new(matches.is_present("dry_run"));
     let config = Config::read(&base_dirs)?;
     let mut report = Report::new();
 
@@ -95,22 +120,6 @@ fn run() -> Result<(), Error> {
     #[cfg(windows)]
     let powershell = windows::Powershell::new();
 
-    #[cfg(windows)]
-    report.push_result(powershell.update_modules(&mut terminal));
-
-    #[cfg(target_os = "linux")]
-    {
-        if !(matches.is_present("no_system")) {
-            report.push_result(linux::upgrade(&sudo, &mut terminal));
-        }
-    }
-
-    #[cfg(windows)]
-    report.push_result(windows::run_chocolatey(&mut terminal));
-
-    #[cfg(unix)]
-    report.push_result(unix::run_homebrew(&mut terminal));
-
     git_repos.insert(base_dirs.home_dir().join(".emacs.d"));
     git_repos.insert(base_dirs.home_dir().join(".vim"));
     git_repos.insert(base_dirs.home_dir().join(".config/nvim"));
@@ -136,58 +145,167 @@ fn run() -> Result<(), Error> {
         }
     }
 
-    for repo in git_repos.repositories() {
-        report.push_result(git.pull(&repo, &mut terminal));
-    }
+    let repos = git_repos.repositories();
+    let no_system = matches.is_present("no_system");
+
+    let mut steps: Vec<Step> = Vec::new();
+
+    #[cfg(windows)]
+    steps.push(Step::new("powershell_modules", |terminal, report| {
+        report.push_result(powershell.update_modules(terminal), StepCategory::PackageManager);
+    }));
+
+    #[cfg(target_os = "linux")]
+    steps.push(Step::new("system", |terminal, report| {
+        if !no_system {
+            report.push_result(linux::upgrade(&sudo, terminal), StepCategory::SystemUpgrade);
+        }
+    }));
+
+    #[cfg(windows)]
+    steps.push(Step::new("chocolatey", |terminal, report| {
+        report.push_result(windows::run_chocolatey(terminal), StepCategory::PackageManager);
+    }));
+
+    #[cfg(unix)]
+    steps.push(Step::new("homebrew", |terminal, report| {
+        report.push_result(unix::run_homebrew(terminal), StepCategory::PackageManager);
+    }));
+
+    steps.push(Step::new("git", |terminal, report| {
+        for repo in &repos {
+            report.push_result(git.pull(repo, terminal), StepCategory::Git);
+        }
+    }));
 
     #[cfg(unix)]
     {
-        report.push_result(unix::run_zplug(&base_dirs, &mut terminal));
-        report.push_result(unix::run_fisherman(&base_dirs, &mut terminal));
-        report.push_result(unix::run_tpm(&base_dirs, &mut terminal));
+        steps.push(Step::new("zplug", |terminal, report| {
+            report.push_result(unix::run_zplug(&base_dirs, terminal), StepCategory::PackageManager);
+        }));
+        steps.push(Step::new("fisherman", |terminal, report| {
+            report.push_result(unix::run_fisherman(&base_dirs, terminal), StepCategory::PackageManager);
+        }));
+        steps.push(Step::new("tmux_plugins", |terminal, report| {
+            report.push_result(unix::run_tpm(&base_dirs, terminal), StepCategory::PackageManager);
+        }));
     }
 
-    report.push_result(generic::run_rustup(&base_dirs, &mut terminal));
-    report.push_result(generic::run_cargo_update(&base_dirs, &mut terminal));
-    report.push_result(generic::run_emacs(&base_dirs, &mut terminal));
-    report.push_result(vim::upgrade_vim(&base_dirs, &mut terminal));
-    report.push_result(vim::upgrade_neovim(&base_dirs, &mut terminal));
-    report.push_result(node::run_npm_upgrade(&base_dirs, &mut terminal));
-    report.push_result(node::yarn_global_update(&mut terminal));
-    report.push_result(generic::run_apm(&mut terminal));
+    steps.push(Step::new("rustup", |terminal, report| {
+        report.push_result(generic::run_rustup(&base_dirs, terminal), StepCategory::PackageManager);
+    }));
+    steps.push(Step::new("cargo", |terminal, report| {
+        report.push_result(generic::run_cargo_update(&base_dirs, terminal), StepCategory::PackageManager);
+    }));
+    steps.push(Step::new("emacs", |terminal, report| {
+        report.push_result(generic::run_emacs(&base_dirs, terminal), StepCategory::Other);
+    }));
+    steps.push(Step::new("vim", |terminal, report| {
+        report.push_result(vim::upgrade_vim(&base_dirs, terminal), StepCategory::PackageManager);
+    }));
+    steps.push(Step::new("neovim", |terminal, report| {
+        report.push_result(vim::upgrade_neovim(&base_dirs, terminal), StepCategory::PackageManager);
+    }));
+    steps.push(Step::new("npm", |terminal, report| {
+        report.push_result(node::run_npm_upgrade(&base_dirs, terminal), StepCategory::PackageManager);
+    }));
+    steps.push(Step::new("yarn", |terminal, report| {
+        report.push_result(node::yarn_global_update(terminal), StepCategory::PackageManager);
+    }));
+    steps.push(Step::new("apm", |terminal, report| {
+        report.push_result(generic::run_apm(terminal), StepCategory::PackageManager);
+    }));
 
     #[cfg(target_os = "linux")]
     {
-        report.push_result(linux::run_flatpak(&mut terminal));
-        report.push_result(linux::run_snap(&sudo, &mut terminal));
-    }
-
-    if let Some(commands) = config.commands() {
-        for (name, command) in commands {
-            report.push_result(Some((
-                name,
-                generic::run_custom_command(&name, &command, &mut terminal).is_ok(),
-            )));
-        }
+        steps.push(Step::new("flatpak", |terminal, report| {
+            report.push_result(linux::run_flatpak(terminal), StepCategory::PackageManager);
+        }));
+        steps.push(Step::new("snap", |terminal, report| {
+            report.push_result(linux::run_snap(&sudo, terminal), StepCategory::PackageManager);
+        }));
     }
 
     #[cfg(target_os = "linux")]
     {
-        report.push_result(linux::run_fwupdmgr(&mut terminal));
-        report.push_result(linux::run_needrestart(&sudo, &mut terminal));
+        steps.push(Step::new("fwupdmgr", |terminal, report| {
+            report.push_result(linux::run_fwupdmgr(terminal), StepCategory::SystemUpgrade);
+        }));
+        steps.push(Step::new("needrestart", |terminal, report| {
+            report.push_result(linux::run_needrestart(&sudo, terminal), StepCategory::SystemUpgrade);
+        }));
     }
 
     #[cfg(target_os = "macos")]
-    {
-        if !(matches.is_present("no_system")) {
-            report.push_result(macos::upgrade_macos(&mut terminal));
+    steps.push(Step::new("system", |terminal, report| {
+        if !no_system {
+            report.push_result(macos::upgrade_macos(terminal), StepCategory::SystemUpgrade);
         }
-    }
+    }));
 
     #[cfg(windows)]
-    {
-        if !(matches.is_present("no_system")) {
-            report.push_result(powershell.windows_update(&mut terminal));
+    steps.push(Step::new("system", |terminal, report| {
+        if !no_system {
+            report.push_result(powershell.windows_update(terminal), StepCategory::SystemUpgrade);
+        }
+    }));
+
+    if let Some(commands) = config.commands() {
+        for (name, command) in commands {
+            let id = name.clone();
+            steps.push(Step::new(id, move |terminal, report| {
+                match generic::run_custom_command(&name, &command, terminal) {
+                    Ok(true) => report.push_result(Some((name.clone(), true)), StepCategory::CustomCommand),
+                    Ok(false) => (), // no invocation for this platform; don't count it as a step
+                    Err(_) => report.push_result(Some((name.clone(), false)), StepCategory::CustomCommand),
+                }
+            }));
+        }
+    }
+
+    let only: Vec<&str> = matches.value_of("only").map(steps::parse_selectors).unwrap_or_default();
+    let skip: Vec<&str> = matches.value_of("skip").map(steps::parse_selectors).unwrap_or_default();
+    let config_skip = config.skip();
+    let selected = steps::select(steps, &only, &skip, &config_skip);
+
+    // Remember which names each step produced so a later `--retry` pass
+    // can re-run just the steps behind the rows that failed.
+    let mut step_rows: Vec<(&str, Vec<String>)> = Vec::new();
+    for step in &selected {
+        let before = report.data().len();
+        step.run(&mut terminal, &mut report);
+        let rows = report.data()[before..].iter().map(|(name, _)| name.to_string()).collect();
+        step_rows.push((step.id(), rows));
+    }
+
+    if matches.is_present("retry") {
+        let retry_count: usize = matches
+            .value_of("retry")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(1);
+
+        for _ in 0..retry_count {
+            let failed = report.failed_names();
+            if failed.is_empty() {
+                break;
+            }
+
+            let failing_steps: Vec<&str> = step_rows
+                .iter()
+                .filter(|(_, rows)| rows.iter().any(|name| failed.contains(name)))
+                .map(|(id, _)| *id)
+                .collect();
+
+            if failing_steps.is_empty() {
+                break;
+            }
+
+            for step in &selected {
+                if failing_steps.contains(&step.id()) {
+                    terminal.print_separator(format!("Retrying {}", step.id()));
+                    step.run(&mut terminal, &mut report);
+                }
+            }
         }
     }
 
@@ -195,28 +313,21 @@ fn run() -> Result<(), Error> {
         terminal.print_separator("Summary");
 
         for (key, succeeded) in report.data() {
-            terminal.print_result(key, *succeeded);
+            terminal.print_result(key, succeeded);
         }
     }
 
-    if report.data().iter().all(|(_, succeeded)| *succeeded) {
-        Ok(())
-    } else {
-        Err(StepFailed.into())
-    }
+    Ok(report.exit_code())
 }
 
 fn main() {
     match run() {
-        Ok(()) => {
-            exit(0);
+        Ok(exit_code) => {
+            exit(exit_code.code());
         }
         Err(error) => {
-            match error.downcast::<StepFailed>() {
-                Ok(_) => (),
-                Err(error) => println!("ERROR: {}", error),
-            };
-            exit(1);
+            println!("ERROR: {}", error);
+            exit(AppExitCode::MixedFailures.code());
         }
     }
 }
@@ -0,0 +1,32 @@
+use crate::commands::ShellCommand;
+use crate::terminal::Terminal;
+use crate::utils;
+use directories::BaseDirs;
+
+pub fn run_npm_upgrade(base_dirs: &BaseDirs, terminal: &mut Terminal) -> Option<(String, bool)> {
+    let npm = utils::which("npm")?;
+    let npm_dir = base_dirs.home_dir().join(".npm-packages");
+    if !npm_dir.exists() {
+        return None;
+    }
+    terminal.print_separator("npm");
+
+    let success = ShellCommand::new(npm.to_str()?)
+        .args(&["update", "-g"])
+        .run(terminal)
+        .is_ok();
+
+    Some(("npm".to_string(), success))
+}
+
+pub fn yarn_global_update(terminal: &mut Terminal) -> Option<(String, bool)> {
+    let yarn = utils::which("yarn")?;
+    terminal.print_separator("Yarn");
+
+    let success = ShellCommand::new(yarn.to_str()?)
+        .args(&["global", "upgrade"])
+        .run(terminal)
+        .is_ok();
+
+    Some(("Yarn".to_string(), success))
+}
@@ -0,0 +1,52 @@
+use crate::exitcode::{self, AppExitCode, StepCategory};
+
+/// Accumulates the `(step name, succeeded, category)` triples produced
+/// over the course of a run so the final summary can be printed in one
+/// place and the exit code can be derived from them.
+pub struct Report {
+    data: Vec<(String, bool, StepCategory)>,
+}
+
+impl Report {
+    pub fn new() -> Self {
+        Report { data: Vec::new() }
+    }
+
+    /// Record a step's result. If a result for the same name already
+    /// exists (as happens when `--retry` re-runs a failed step), it is
+    /// updated in place rather than duplicated.
+    pub fn push_result(&mut self, result: Option<(String, bool)>, category: StepCategory) {
+        if let Some((name, succeeded)) = result {
+            match self.data.iter_mut().find(|(existing, _, _)| *existing == name) {
+                Some(entry) => *entry = (name, succeeded, category),
+                None => self.data.push((name, succeeded, category)),
+            }
+        }
+    }
+
+    pub fn data(&self) -> Vec<(&str, bool)> {
+        self.data.iter().map(|(name, succeeded, _)| (name.as_str(), *succeeded)).collect()
+    }
+
+    /// Names of the steps that failed, for `--retry` to act on.
+    pub fn failed_names(&self) -> Vec<String> {
+        self.data
+            .iter()
+            .filter(|(_, succeeded, _)| !succeeded)
+            .map(|(name, _, _)| name.clone())
+            .collect()
+    }
+
+    /// The exit code for the run: `Success` if every step succeeded,
+    /// otherwise the code for the highest-priority failing category.
+    pub fn exit_code(&self) -> AppExitCode {
+        let failed_categories: Vec<StepCategory> = self
+            .data
+            .iter()
+            .filter(|(_, succeeded, _)| !succeeded)
+            .map(|(_, _, category)| *category)
+            .collect();
+
+        exitcode::highest_priority_failure(&failed_categories)
+    }
+}
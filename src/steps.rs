@@ -0,0 +1,96 @@
+use crate::report::Report;
+use crate::terminal::Terminal;
+
+/// A single named step in a run. `id` is the stable identifier used by
+/// `--only`, `--skip`, and the config's `[skip]` list to select it. A step
+/// is free to push zero, one, or several results into the `Report` (a
+/// "git" step, for instance, reports once per repository).
+pub struct Step<'a> {
+    id: String,
+    run: Box<dyn Fn(&mut Terminal, &mut Report) + 'a>,
+}
+
+impl<'a> Step<'a> {
+    pub fn new<S, F>(id: S, run: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&mut Terminal, &mut Report) + 'a,
+    {
+        Step {
+            id: id.into(),
+            run: Box::new(run),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Steps take `&self` rather than consuming it so `--retry` can run the
+    /// same step again.
+    pub fn run(&self, terminal: &mut Terminal, report: &mut Report) {
+        (self.run)(terminal, report)
+    }
+}
+
+/// Parse a comma-separated `--only`/`--skip` value into the set of step
+/// ids it names.
+pub fn parse_selectors(raw: &str) -> Vec<&str> {
+    raw.split(',').map(str::trim).filter(|s| !s.is_empty()).collect()
+}
+
+/// Keep only the steps selected by `--only`/`--skip` and the config
+/// `[skip]` list. `only` takes precedence: if given, every other step is
+/// dropped; otherwise steps named by `skip` or `config_skip` are dropped.
+pub fn select<'a>(steps: Vec<Step<'a>>, only: &[&str], skip: &[&str], config_skip: &[String]) -> Vec<Step<'a>> {
+    steps
+        .into_iter()
+        .filter(|step| {
+            if !only.is_empty() {
+                only.contains(&step.id())
+            } else {
+                !skip.contains(&step.id()) && !config_skip.iter().any(|s| s == step.id())
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(id: &'static str) -> Step<'static> {
+        Step::new(id, |_, _| {})
+    }
+
+    fn ids<'a>(steps: &'a [Step]) -> Vec<&'a str> {
+        steps.iter().map(Step::id).collect()
+    }
+
+    #[test]
+    fn parse_selectors_splits_and_trims() {
+        assert_eq!(parse_selectors("git, cargo ,, npm"), vec!["git", "cargo", "npm"]);
+        assert_eq!(parse_selectors(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn select_with_only_drops_everything_else() {
+        let steps = vec![step("git"), step("cargo"), step("npm")];
+        let selected = select(steps, &["git", "npm"], &["git"], &["npm".to_string()]);
+        assert_eq!(ids(&selected), vec!["git", "npm"]);
+    }
+
+    #[test]
+    fn select_without_only_applies_skip_and_config_skip() {
+        let steps = vec![step("git"), step("cargo"), step("npm")];
+        let selected = select(steps, &[], &["cargo"], &["npm".to_string()]);
+        assert_eq!(ids(&selected), vec!["git"]);
+    }
+
+    #[test]
+    fn select_with_no_selectors_keeps_everything() {
+        let steps = vec![step("git"), step("cargo")];
+        let selected = select(steps, &[], &[], &[]);
+        assert_eq!(ids(&selected), vec!["git", "cargo"]);
+    }
+}
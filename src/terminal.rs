@@ -0,0 +1,74 @@
+use std::io::Write;
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+/// Thin wrapper around a colored stdout used by every step to print section
+/// separators and the final summary. Also carries the `--dry-run` flag so
+/// that `ShellCommand` and the summary can tell a preview run from a real
+/// one without threading an extra argument through every step function.
+pub struct Terminal {
+    stdout: StandardStream,
+    dry_run: bool,
+}
+
+impl Terminal {
+    pub fn new(dry_run: bool) -> Self {
+        Terminal {
+            stdout: StandardStream::stdout(ColorChoice::Auto),
+            dry_run,
+        }
+    }
+
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    pub fn print_separator<P: AsRef<str>>(&mut self, message: P) {
+        let message = message.as_ref();
+        let width = term_size::dimensions().map(|(w, _)| w).unwrap_or(80);
+        let mut color = ColorSpec::new();
+        color.set_fg(Some(Color::Blue)).set_bold(true);
+
+        let _ = self.stdout.set_color(&color);
+        println!();
+        let _ = writeln!(
+            self.stdout,
+            "── {} {}",
+            message,
+            "─".repeat(width.saturating_sub(message.len() + 4))
+        );
+        let _ = self.stdout.reset();
+    }
+
+    /// Print a plain, uncolored line, e.g. a command preview in `--dry-run`
+    /// mode. Unlike `print_separator` this doesn't draw a full-width bar, so
+    /// several of these can stack under a single step separator.
+    pub fn print_line<P: AsRef<str>>(&mut self, message: P) {
+        let _ = writeln!(self.stdout, "{}", message.as_ref());
+    }
+
+    pub fn print_result<P: AsRef<str>>(&mut self, key: P, succeeded: bool) {
+        let mut color = ColorSpec::new();
+        color.set_fg(Some(if succeeded { Color::Green } else { Color::Red }));
+
+        let status = if self.dry_run {
+            "would run"
+        } else if succeeded {
+            "OK"
+        } else {
+            "FAILED"
+        };
+
+        let _ = self.stdout.set_color(&color);
+        let _ = writeln!(self.stdout, "{}: {}", key.as_ref(), status);
+        let _ = self.stdout.reset();
+    }
+
+    pub fn print_error<P: AsRef<str>>(&mut self, message: P) {
+        let mut color = ColorSpec::new();
+        color.set_fg(Some(Color::Red)).set_bold(true);
+
+        let _ = self.stdout.set_color(&color);
+        let _ = writeln!(self.stdout, "{}", message.as_ref());
+        let _ = self.stdout.reset();
+    }
+}
@@ -0,0 +1,83 @@
+use crate::commands::ShellCommand;
+use crate::terminal::Terminal;
+use crate::utils;
+use directories::BaseDirs;
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+
+/// Re-exec the current binary inside a new tmux session so the rest of the
+/// run survives the terminal being closed.
+pub fn run_in_tmux() -> ! {
+    let tmux = utils::which("tmux").expect("tmux not found");
+    let args: Vec<String> = std::env::args().collect();
+
+    let error = Command::new(tmux)
+        .args(&["new-session", "-s", "topgrade"])
+        .arg(args.join(" "))
+        .exec();
+
+    panic!("Failed to execute tmux: {}", error);
+}
+
+pub fn run_homebrew(terminal: &mut Terminal) -> Option<(String, bool)> {
+    let brew = utils::which("brew")?;
+    terminal.print_separator("Homebrew");
+
+    let success = ShellCommand::new(brew.to_str()?)
+        .args(&["update"])
+        .run(terminal)
+        .is_ok()
+        && ShellCommand::new(brew.to_str()?)
+            .args(&["upgrade"])
+            .run(terminal)
+            .is_ok();
+
+    Some(("Homebrew".to_string(), success))
+}
+
+pub fn run_zplug(base_dirs: &BaseDirs, terminal: &mut Terminal) -> Option<(String, bool)> {
+    let zshrc = base_dirs.home_dir().join(".zshrc");
+    if !zshrc.exists() {
+        return None;
+    }
+    let zsh = utils::which("zsh")?;
+    terminal.print_separator("zplug");
+
+    let success = ShellCommand::new(zsh.to_str()?)
+        .args(&["-c", "source ~/.zshrc && zplug update"])
+        .run(terminal)
+        .is_ok();
+
+    Some(("zplug".to_string(), success))
+}
+
+pub fn run_fisherman(base_dirs: &BaseDirs, terminal: &mut Terminal) -> Option<(String, bool)> {
+    let fish_config = base_dirs.home_dir().join(".config/fish");
+    if !fish_config.exists() {
+        return None;
+    }
+    let fish = utils::which("fish")?;
+    terminal.print_separator("fisherman");
+
+    let success = ShellCommand::new(fish.to_str()?)
+        .args(&["-c", "fisher update"])
+        .run(terminal)
+        .is_ok();
+
+    Some(("fisherman".to_string(), success))
+}
+
+pub fn run_tpm(base_dirs: &BaseDirs, terminal: &mut Terminal) -> Option<(String, bool)> {
+    let tpm = base_dirs.home_dir().join(".tmux/plugins/tpm/bin/update_plugins");
+    if !tpm.exists() {
+        return None;
+    }
+    terminal.print_separator("tmux plugins");
+
+    let success = ShellCommand::new(tpm.to_str()?)
+        .args(&["all"])
+        .run(terminal)
+        .is_ok();
+
+    Some(("tmux plugins".to_string(), success))
+}
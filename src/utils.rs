@@ -0,0 +1,8 @@
+use std::path::PathBuf;
+
+/// Look up `binary_name` on `PATH`, returning `None` instead of an error
+/// when it cannot be found so callers can simply skip steps whose tool
+/// isn't installed.
+pub fn which(binary_name: &str) -> Option<PathBuf> {
+    which::which(binary_name).ok()
+}
@@ -0,0 +1,40 @@
+use crate::commands::ShellCommand;
+use crate::terminal::Terminal;
+use crate::utils;
+use directories::BaseDirs;
+
+const UPGRADE_VIM_PLUGINS: &str = "try | PlugUpgrade | silent! PlugUpdate --sync | q | endtry";
+
+pub fn upgrade_vim(base_dirs: &BaseDirs, terminal: &mut Terminal) -> Option<(String, bool)> {
+    let plug_file = base_dirs.home_dir().join(".vim/autoload/plug.vim");
+    if !plug_file.exists() {
+        return None;
+    }
+
+    let vim = utils::which("vim")?;
+    terminal.print_separator("Vim");
+
+    let success = ShellCommand::new(vim.to_str()?)
+        .args(&["-N", "-u", base_dirs.home_dir().join(".vimrc").to_str()?, "-c", UPGRADE_VIM_PLUGINS])
+        .run(terminal)
+        .is_ok();
+
+    Some(("Vim".to_string(), success))
+}
+
+pub fn upgrade_neovim(base_dirs: &BaseDirs, terminal: &mut Terminal) -> Option<(String, bool)> {
+    let plug_file = base_dirs.home_dir().join(".local/share/nvim/site/autoload/plug.vim");
+    if !plug_file.exists() {
+        return None;
+    }
+
+    let nvim = utils::which("nvim")?;
+    terminal.print_separator("Neovim");
+
+    let success = ShellCommand::new(nvim.to_str()?)
+        .args(&["+PlugUpgrade", "+PlugUpdate", "+qa"])
+        .run(terminal)
+        .is_ok();
+
+    Some(("Neovim".to_string(), success))
+}
@@ -0,0 +1,67 @@
+use crate::commands::ShellCommand;
+use crate::terminal::Terminal;
+use crate::utils;
+use directories::UserDirs;
+use std::path::PathBuf;
+
+pub struct Powershell {
+    powershell: Option<PathBuf>,
+}
+
+impl Powershell {
+    pub fn new() -> Self {
+        Powershell {
+            powershell: utils::which("powershell"),
+        }
+    }
+
+    pub fn profile(&self) -> Option<PathBuf> {
+        self.powershell.as_ref()?;
+        let user_dirs = UserDirs::new()?;
+        Some(
+            user_dirs
+                .document_dir()?
+                .join("WindowsPowerShell")
+                .join("Microsoft.PowerShell_profile.ps1"),
+        )
+    }
+
+    pub fn update_modules(&self, terminal: &mut Terminal) -> Option<(String, bool)> {
+        let powershell = self.powershell.as_ref()?;
+        terminal.print_separator("Powershell Modules Update");
+
+        let success = ShellCommand::new(powershell.to_str()?)
+            .args(&["-Command", "Update-Module"])
+            .run(terminal)
+            .is_ok();
+
+        Some(("Powershell Modules Update".to_string(), success))
+    }
+
+    pub fn windows_update(&self, terminal: &mut Terminal) -> Option<(String, bool)> {
+        let powershell = self.powershell.as_ref()?;
+        terminal.print_separator("Windows Update");
+
+        let success = ShellCommand::new(powershell.to_str()?)
+            .args(&[
+                "-Command",
+                "Install-Module PSWindowsUpdate -Force; Install-WindowsUpdate -AcceptAll",
+            ])
+            .run(terminal)
+            .is_ok();
+
+        Some(("Windows Update".to_string(), success))
+    }
+}
+
+pub fn run_chocolatey(terminal: &mut Terminal) -> Option<(String, bool)> {
+    let choco = utils::which("choco")?;
+    terminal.print_separator("Chocolatey");
+
+    let success = ShellCommand::new(choco.to_str()?)
+        .args(&["upgrade", "all"])
+        .run(terminal)
+        .is_ok();
+
+    Some(("Chocolatey".to_string(), success))
+}